@@ -1,18 +1,68 @@
 use eframe::egui;
-use rdev::{listen, Event, EventType};
+use rdev::{listen, simulate, Button, Event, EventType, Key};
 use std::fs::File;
 use std::io::Write;
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use chrono::Local;
 use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
 use simple_logger::SimpleLogger;
 
+mod net;
+mod session;
+mod triggers;
+
+use session::ExportFormat;
+use triggers::TriggerRule;
+
 fn main() -> Result<(), eframe::Error> {
     // Initialize the logger
     SimpleLogger::new().init().unwrap();
     info!("Logger initialized.");
 
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("--server") => {
+            let secret = match args.get(2) {
+                Some(secret) => secret.as_str(),
+                None => {
+                    error!("--server requires a shared secret, e.g. --server mysecret [addr]");
+                    return Ok(());
+                }
+            };
+            let addr = args.get(3).map(String::as_str).unwrap_or("127.0.0.1:7878");
+            info!("Starting in server (streaming) mode on {}", addr);
+            if let Err(err) = net::run_server(addr, secret) {
+                error!("Server mode exited with error: {:?}", err);
+            }
+            return Ok(());
+        }
+        Some("--client") => {
+            let addr = match args.get(2) {
+                Some(addr) => addr.as_str(),
+                None => {
+                    error!("--client requires a server address, e.g. --client 192.168.1.10:7878 mysecret");
+                    return Ok(());
+                }
+            };
+            let secret = match args.get(3) {
+                Some(secret) => secret.as_str(),
+                None => {
+                    error!("--client requires the server's shared secret, e.g. --client 192.168.1.10:7878 mysecret");
+                    return Ok(());
+                }
+            };
+            info!("Starting in client (streaming) mode, connecting to {}", addr);
+            if let Err(err) = net::run_client(addr, secret) {
+                error!("Client mode exited with error: {:?}", err);
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([400.0, 200.0]), // Set initial window size
@@ -30,92 +80,323 @@ fn main() -> Result<(), eframe::Error> {
     )
 }
 
+/// Global hotkey combination that toggles recording on/off: Ctrl+Shift+R.
+const RECORD_TOGGLE_HOTKEY: [Key; 3] = [Key::ControlLeft, Key::ShiftLeft, Key::KeyR];
+
 struct TaskRecorderApp {
     task_name: String,
-    is_recording: bool,
+    task_name_shared: Arc<Mutex<String>>,
+    is_recording: Arc<Mutex<bool>>,
     events: Arc<Mutex<Vec<EventData>>>,
+    is_playing: Arc<Mutex<bool>>,
+    recording_start: Arc<Mutex<Option<Instant>>>,
+    export_format: ExportFormat,
+    export_format_shared: Arc<Mutex<ExportFormat>>,
+    load_path: String,
 }
 
-struct EventData {
-    event_type: String,
-    button_or_key: String,
-    action: String,
-    position: String,
-    timestamp: String,
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct EventData {
+    pub(crate) event_type: String,
+    pub(crate) button_or_key: String,
+    pub(crate) action: String,
+    pub(crate) position: String,
+    pub(crate) timestamp: String,
+    /// Monotonic offset from the start of the recording, in microseconds.
+    pub(crate) offset_micros: u64,
+    /// Absolute UNIX epoch timestamp, in microseconds.
+    pub(crate) epoch_micros: u64,
 }
 
 impl Default for TaskRecorderApp {
     fn default() -> Self {
         info!("TaskRecorderApp initialized.");
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let is_recording = Arc::new(Mutex::new(false));
+        let is_playing = Arc::new(Mutex::new(false));
+        let recording_start = Arc::new(Mutex::new(None));
+        let task_name_shared = Arc::new(Mutex::new(String::new()));
+        let trigger_rules = Arc::new(Mutex::new(triggers::load_from_file("triggers.conf")));
+        let export_format_shared = Arc::new(Mutex::new(ExportFormat::Csv));
+
+        spawn_global_listener(
+            Arc::clone(&events),
+            Arc::clone(&is_recording),
+            Arc::clone(&is_playing),
+            Arc::clone(&recording_start),
+            Arc::clone(&task_name_shared),
+            trigger_rules,
+            Arc::clone(&export_format_shared),
+        );
+
         Self {
             task_name: String::new(),
-            is_recording: false,
-            events: Arc::new(Mutex::new(Vec::new())),
+            task_name_shared,
+            is_recording,
+            events,
+            is_playing,
+            recording_start,
+            export_format: ExportFormat::Csv,
+            export_format_shared,
+            load_path: String::new(),
         }
     }
 }
 
 impl TaskRecorderApp {
     fn start_recording(&mut self) {
+        if *self.is_playing.lock().unwrap() {
+            warn!("Cannot start recording while playback is in progress.");
+            return;
+        }
+        let mut is_recording = self.is_recording.lock().unwrap();
+        if *is_recording {
+            warn!("Already recording.");
+            return;
+        }
         info!("Starting recording...");
-        self.is_recording = true;
+        *is_recording = true;
+        drop(is_recording);
+
         self.events.lock().unwrap().clear();
+        *self.recording_start.lock().unwrap() = Some(Instant::now());
+    }
+
+    fn stop_recording(&mut self) {
+        let mut is_recording = self.is_recording.lock().unwrap();
+        if !*is_recording {
+            return;
+        }
+        info!("Stopping recording...");
+        *is_recording = false;
+        drop(is_recording);
 
-        let events = Arc::clone(&self.events);
+        *self.recording_start.lock().unwrap() = None;
+        self.save_session();
+    }
+
+    fn start_playback(&mut self) {
+        if *self.is_recording.lock().unwrap() {
+            warn!("Cannot start playback while recording is active.");
+            return;
+        }
+        if *self.is_playing.lock().unwrap() {
+            warn!("Playback already in progress.");
+            return;
+        }
+
+        let events = self.events.lock().unwrap().clone();
+        if events.is_empty() {
+            warn!("No recorded events to replay.");
+            return;
+        }
+
+        info!("Starting playback of {} events...", events.len());
+        *self.is_playing.lock().unwrap() = true;
+
+        let is_playing = Arc::clone(&self.is_playing);
         thread::spawn(move || {
-            info!("Event listener thread started.");
-            if let Err(error) = listen(move |event| {
-                if let Some(event_data) = process_event(event) {
-                    events.lock().unwrap().push(event_data);
+            let mut last_offset_micros = 0u64;
+            for event in events.iter() {
+                let delay = Duration::from_micros(
+                    event.offset_micros.saturating_sub(last_offset_micros),
+                );
+                if delay > Duration::ZERO {
+                    thread::sleep(delay);
+                }
+                last_offset_micros = event.offset_micros;
+
+                match event_data_to_event_type(event) {
+                    Some(event_type) => {
+                        if let Err(err) = simulate(&event_type) {
+                            error!("Failed to replay event {:?}: {:?}", event_type, err);
+                        }
+                    }
+                    None => {
+                        warn!(
+                            "Skipping unrecognized event during playback: {} {}",
+                            event.event_type, event.button_or_key
+                        );
+                    }
                 }
-            }) {
-                error!("Error in event listener: {:?}", error);
             }
-            warn!("Event listener thread exited.");
+            info!("Playback finished.");
+            *is_playing.lock().unwrap() = false;
         });
     }
 
-    fn stop_recording(&mut self) {
-        info!("Stopping recording...");
-        self.is_recording = false;
-        self.save_to_csv();
+    fn save_session(&self) {
+        *self.task_name_shared.lock().unwrap() = self.task_name.clone();
+        session::save(&self.task_name, &self.events.lock().unwrap(), self.export_format);
     }
 
-    fn save_to_csv(&self) {
-        let filename = format!("{}_events.csv", self.task_name);
-        info!("Saving events to file: {}", filename);
-
-        let mut file = match File::create(&filename) {
-            Ok(file) => file,
-            Err(err) => {
-                error!("Failed to create CSV file: {:?}", err);
-                return;
+    /// Loads a previously saved `.json` or `.ron` session, replacing the current events so
+    /// it can be fed straight into `start_playback`.
+    fn load_session(&mut self) {
+        if self.load_path.is_empty() {
+            warn!("No session path given to load.");
+            return;
+        }
+        match session::load(&self.load_path) {
+            Some((task_name, events)) => {
+                self.task_name = task_name;
+                *self.events.lock().unwrap() = events;
             }
-        };
+            None => warn!("Failed to load session from {}", self.load_path),
+        }
+    }
+}
+
+pub(crate) fn save_events_to_csv(task_name: &str, events: &[EventData]) {
+    let filename = format!("{}_events.csv", task_name);
+    info!("Saving events to file: {}", filename);
 
-        let events = self.events.lock().unwrap();
-        if let Err(err) = writeln!(file, "Event Type,Button/Key,Action,Position,Timestamp") {
-            error!("Failed to write to CSV file: {:?}", err);
+    let mut file = match File::create(&filename) {
+        Ok(file) => file,
+        Err(err) => {
+            error!("Failed to create CSV file: {:?}", err);
             return;
         }
+    };
+
+    if let Err(err) = writeln!(
+        file,
+        "Event Type,Button/Key,Action,Position,Timestamp,OffsetMicros,EpochMicros"
+    ) {
+        error!("Failed to write to CSV file: {:?}", err);
+        return;
+    }
+
+    for event in events.iter() {
+        if let Err(err) = writeln!(
+            file,
+            "{},({}),{},{},{},{},{}",
+            event.event_type,
+            event.button_or_key,
+            event.action,
+            event.position,
+            event.timestamp,
+            event.offset_micros,
+            event.epoch_micros
+        ) {
+            error!("Failed to write event to CSV file: {:?}", err);
+            return;
+        }
+    }
 
-        for event in events.iter() {
-            if let Err(err) = writeln!(
-                file,
-                "{},({}),{},{},{}",
-                event.event_type, event.button_or_key, event.action, event.position, event.timestamp
-            ) {
-                error!("Failed to write event to CSV file: {:?}", err);
-                return;
+    info!("Events saved to {}", filename);
+}
+
+/// Starts the single persistent listener the whole app shares: it tracks currently-pressed
+/// keys to detect the [`RECORD_TOGGLE_HOTKEY`] chord, and while `is_recording` is set,
+/// forwards events into `events` exactly like a button-driven recording session would.
+fn spawn_global_listener(
+    events: Arc<Mutex<Vec<EventData>>>,
+    is_recording: Arc<Mutex<bool>>,
+    is_playing: Arc<Mutex<bool>>,
+    recording_start: Arc<Mutex<Option<Instant>>>,
+    task_name: Arc<Mutex<String>>,
+    trigger_rules: Arc<Mutex<Vec<TriggerRule>>>,
+    export_format: Arc<Mutex<ExportFormat>>,
+) {
+    thread::spawn(move || {
+        info!("Global hotkey listener thread started.");
+        let last_position = Arc::new(Mutex::new((0.0, 0.0)));
+        let mut pressed_keys: Vec<Key> = Vec::new();
+
+        if let Err(error) = listen(move |event| {
+            match &event.event_type {
+                EventType::KeyPress(key) => {
+                    let key = *key;
+                    if !pressed_keys.contains(&key) {
+                        pressed_keys.push(key);
+                        if chord_matches(&pressed_keys, &RECORD_TOGGLE_HOTKEY) {
+                            toggle_recording(
+                                &is_recording,
+                                &is_playing,
+                                &events,
+                                &recording_start,
+                                &task_name,
+                                &export_format,
+                            );
+                        }
+                    }
+                }
+                EventType::KeyRelease(key) => {
+                    pressed_keys.retain(|k| k != key);
+                }
+                _ => {}
+            }
+
+            // Only capture/evaluate while actually recording: besides being a needless
+            // logging/CPU cost the rest of the time, `start_playback` and the streaming
+            // client both replay events with `simulate()`, which on Linux loops back
+            // through this same `listen()`. `start_recording` and `toggle_recording` both
+            // refuse to start a recording while `is_playing` is set, so gating on
+            // `is_recording` here also keeps replayed events from re-firing triggers or
+            // getting re-recorded into the session they came from.
+            if *is_recording.lock().unwrap() {
+                let start = recording_start.lock().unwrap().unwrap_or_else(Instant::now);
+                if let Some(event_data) = process_event(event, start, &last_position) {
+                    triggers::evaluate(&trigger_rules.lock().unwrap(), &event_data);
+                    events.lock().unwrap().push(event_data);
+                }
             }
+        }) {
+            error!("Error in global hotkey listener: {:?}", error);
         }
+        warn!("Global hotkey listener thread exited.");
+    });
+}
 
-        info!("Events saved to {}", filename);
+/// Flips the shared recording flag. Turning recording on clears the event buffer and starts
+/// a fresh timing baseline; turning it off saves the session, mirroring what the "Stop Task"
+/// button does.
+fn toggle_recording(
+    is_recording: &Arc<Mutex<bool>>,
+    is_playing: &Arc<Mutex<bool>>,
+    events: &Arc<Mutex<Vec<EventData>>>,
+    recording_start: &Arc<Mutex<Option<Instant>>>,
+    task_name: &Arc<Mutex<String>>,
+    export_format: &Arc<Mutex<ExportFormat>>,
+) {
+    let mut recording = is_recording.lock().unwrap();
+    if !*recording && *is_playing.lock().unwrap() {
+        warn!("Ignoring hotkey: cannot start recording while playback is in progress.");
+        return;
     }
+    *recording = !*recording;
+    if *recording {
+        info!("Hotkey toggled recording on.");
+        events.lock().unwrap().clear();
+        *recording_start.lock().unwrap() = Some(Instant::now());
+    } else {
+        info!("Hotkey toggled recording off.");
+        *recording_start.lock().unwrap() = None;
+        session::save(
+            &task_name.lock().unwrap(),
+            &events.lock().unwrap(),
+            *export_format.lock().unwrap(),
+        );
+    }
+}
+
+/// True when `pressed` contains exactly the keys in `combo`, regardless of press order.
+fn chord_matches(pressed: &[Key], combo: &[Key]) -> bool {
+    pressed.len() == combo.len() && combo.iter().all(|key| pressed.contains(key))
 }
 
-fn process_event(event: Event) -> Option<EventData> {
+pub(crate) fn process_event(
+    event: Event,
+    start: Instant,
+    last_position: &Arc<Mutex<(f64, f64)>>,
+) -> Option<EventData> {
     let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let offset_micros = start.elapsed().as_micros() as u64;
+    let epoch_micros = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_micros() as u64;
     match event.event_type {
         EventType::KeyPress(key) => {
             info!("Key pressed: {:?}", key);
@@ -125,6 +406,8 @@ fn process_event(event: Event) -> Option<EventData> {
                 action: "Pressed".to_string(),
                 position: "N/A".to_string(),
                 timestamp,
+                offset_micros,
+                epoch_micros,
             })
         }
         EventType::KeyRelease(key) => {
@@ -135,44 +418,221 @@ fn process_event(event: Event) -> Option<EventData> {
                 action: "Released".to_string(),
                 position: "N/A".to_string(),
                 timestamp,
+                offset_micros,
+                epoch_micros,
             })
         }
         EventType::ButtonPress(button) => {
             info!("Mouse button pressed: {:?}", button);
+            let (x, y) = *last_position.lock().unwrap();
             Some(EventData {
                 event_type: "Mouse".to_string(),
                 button_or_key: format!("{:?}", button),
                 action: "Pressed".to_string(),
-                position: "N/A".to_string(), // Mouse position is not available here
+                position: format!("({}, {})", x, y),
                 timestamp,
+                offset_micros,
+                epoch_micros,
             })
         }
         EventType::ButtonRelease(button) => {
             info!("Mouse button released: {:?}", button);
+            let (x, y) = *last_position.lock().unwrap();
             Some(EventData {
                 event_type: "Mouse".to_string(),
                 button_or_key: format!("{:?}", button),
                 action: "Released".to_string(),
-                position: "N/A".to_string(), // Mouse position is not available here
+                position: format!("({}, {})", x, y),
                 timestamp,
+                offset_micros,
+                epoch_micros,
             })
         }
         EventType::MouseMove { x, y } => {
             info!("Mouse moved to: ({}, {})", x, y);
+            *last_position.lock().unwrap() = (x, y);
             Some(EventData {
                 event_type: "Mouse".to_string(),
                 button_or_key: "Move".to_string(),
                 action: "Moved".to_string(),
                 position: format!("({}, {})", x, y),
                 timestamp,
+                offset_micros,
+                epoch_micros,
             })
         }
         _ => None,
     }
 }
 
+/// Maps a recorded [`EventData`] back to the `rdev` event it came from, so it can be
+/// replayed with `simulate`. Only the keys/buttons we commonly record are recognized;
+/// anything else returns `None` so the caller can log and skip it rather than aborting
+/// the whole replay.
+pub(crate) fn event_data_to_event_type(data: &EventData) -> Option<EventType> {
+    match data.event_type.as_str() {
+        "Keyboard" => {
+            let key = key_from_debug_str(&data.button_or_key)?;
+            match data.action.as_str() {
+                "Pressed" => Some(EventType::KeyPress(key)),
+                "Released" => Some(EventType::KeyRelease(key)),
+                _ => None,
+            }
+        }
+        "Mouse" => {
+            if data.button_or_key == "Move" {
+                let (x, y) = parse_position(&data.position)?;
+                return Some(EventType::MouseMove { x, y });
+            }
+            let button = button_from_debug_str(&data.button_or_key)?;
+            match data.action.as_str() {
+                "Pressed" => Some(EventType::ButtonPress(button)),
+                "Released" => Some(EventType::ButtonRelease(button)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn key_from_debug_str(s: &str) -> Option<Key> {
+    Some(match s {
+        "Alt" => Key::Alt,
+        "AltGr" => Key::AltGr,
+        "Backspace" => Key::Backspace,
+        "CapsLock" => Key::CapsLock,
+        "ControlLeft" => Key::ControlLeft,
+        "ControlRight" => Key::ControlRight,
+        "Delete" => Key::Delete,
+        "DownArrow" => Key::DownArrow,
+        "End" => Key::End,
+        "Escape" => Key::Escape,
+        "Home" => Key::Home,
+        "LeftArrow" => Key::LeftArrow,
+        "MetaLeft" => Key::MetaLeft,
+        "MetaRight" => Key::MetaRight,
+        "PageDown" => Key::PageDown,
+        "PageUp" => Key::PageUp,
+        "Return" => Key::Return,
+        "RightArrow" => Key::RightArrow,
+        "ShiftLeft" => Key::ShiftLeft,
+        "ShiftRight" => Key::ShiftRight,
+        "Space" => Key::Space,
+        "Tab" => Key::Tab,
+        "UpArrow" => Key::UpArrow,
+        "KeyA" => Key::KeyA,
+        "KeyB" => Key::KeyB,
+        "KeyC" => Key::KeyC,
+        "KeyD" => Key::KeyD,
+        "KeyE" => Key::KeyE,
+        "KeyF" => Key::KeyF,
+        "KeyG" => Key::KeyG,
+        "KeyH" => Key::KeyH,
+        "KeyI" => Key::KeyI,
+        "KeyJ" => Key::KeyJ,
+        "KeyK" => Key::KeyK,
+        "KeyL" => Key::KeyL,
+        "KeyM" => Key::KeyM,
+        "KeyN" => Key::KeyN,
+        "KeyO" => Key::KeyO,
+        "KeyP" => Key::KeyP,
+        "KeyQ" => Key::KeyQ,
+        "KeyR" => Key::KeyR,
+        "KeyS" => Key::KeyS,
+        "KeyT" => Key::KeyT,
+        "KeyU" => Key::KeyU,
+        "KeyV" => Key::KeyV,
+        "KeyW" => Key::KeyW,
+        "KeyX" => Key::KeyX,
+        "KeyY" => Key::KeyY,
+        "KeyZ" => Key::KeyZ,
+        "Num0" => Key::Num0,
+        "Num1" => Key::Num1,
+        "Num2" => Key::Num2,
+        "Num3" => Key::Num3,
+        "Num4" => Key::Num4,
+        "Num5" => Key::Num5,
+        "Num6" => Key::Num6,
+        "Num7" => Key::Num7,
+        "Num8" => Key::Num8,
+        "Num9" => Key::Num9,
+        "F1" => Key::F1,
+        "F2" => Key::F2,
+        "F3" => Key::F3,
+        "F4" => Key::F4,
+        "F5" => Key::F5,
+        "F6" => Key::F6,
+        "F7" => Key::F7,
+        "F8" => Key::F8,
+        "F9" => Key::F9,
+        "F10" => Key::F10,
+        "F11" => Key::F11,
+        "F12" => Key::F12,
+        "PrintScreen" => Key::PrintScreen,
+        "ScrollLock" => Key::ScrollLock,
+        "Pause" => Key::Pause,
+        "NumLock" => Key::NumLock,
+        "BackQuote" => Key::BackQuote,
+        "Minus" => Key::Minus,
+        "Equal" => Key::Equal,
+        "LeftBracket" => Key::LeftBracket,
+        "RightBracket" => Key::RightBracket,
+        "SemiColon" => Key::SemiColon,
+        "Quote" => Key::Quote,
+        "BackSlash" => Key::BackSlash,
+        "IntlBackslash" => Key::IntlBackslash,
+        "Comma" => Key::Comma,
+        "Dot" => Key::Dot,
+        "Slash" => Key::Slash,
+        "Insert" => Key::Insert,
+        "KpReturn" => Key::KpReturn,
+        "KpMinus" => Key::KpMinus,
+        "KpPlus" => Key::KpPlus,
+        "KpMultiply" => Key::KpMultiply,
+        "KpDivide" => Key::KpDivide,
+        "Kp0" => Key::Kp0,
+        "Kp1" => Key::Kp1,
+        "Kp2" => Key::Kp2,
+        "Kp3" => Key::Kp3,
+        "Kp4" => Key::Kp4,
+        "Kp5" => Key::Kp5,
+        "Kp6" => Key::Kp6,
+        "Kp7" => Key::Kp7,
+        "Kp8" => Key::Kp8,
+        "Kp9" => Key::Kp9,
+        "KpDelete" => Key::KpDelete,
+        "Function" => Key::Function,
+        other => return parse_unknown_debug_str(other).map(Key::Unknown),
+    })
+}
+
+fn button_from_debug_str(s: &str) -> Option<Button> {
+    match s {
+        "Left" => Some(Button::Left),
+        "Right" => Some(Button::Right),
+        "Middle" => Some(Button::Middle),
+        other => parse_unknown_debug_str(other).map(|code| Button::Unknown(code as u8)),
+    }
+}
+
+/// Parses the `Debug` output of `Key::Unknown(u32)`/`Button::Unknown(u8)`, e.g. `"Unknown(65)"`.
+fn parse_unknown_debug_str(s: &str) -> Option<u32> {
+    s.strip_prefix("Unknown(")?.strip_suffix(')')?.parse().ok()
+}
+
+fn parse_position(position: &str) -> Option<(f64, f64)> {
+    let trimmed = position.trim_start_matches('(').trim_end_matches(')');
+    let mut parts = trimmed.split(',').map(|p| p.trim());
+    let x = parts.next()?.parse().ok()?;
+    let y = parts.next()?.parse().ok()?;
+    Some((x, y))
+}
+
 impl eframe::App for TaskRecorderApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        *self.task_name_shared.lock().unwrap() = self.task_name.clone();
+        *self.export_format_shared.lock().unwrap() = self.export_format;
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading("Task Recorder");
 
@@ -181,6 +641,17 @@ impl eframe::App for TaskRecorderApp {
                 ui.text_edit_singleline(&mut self.task_name);
             });
 
+            ui.horizontal(|ui| {
+                ui.label("Export Format:");
+                egui::ComboBox::from_id_source("export_format")
+                    .selected_text(self.export_format.label())
+                    .show_ui(ui, |ui| {
+                        for format in ExportFormat::ALL {
+                            ui.selectable_value(&mut self.export_format, format, format.label());
+                        }
+                    });
+            });
+
             if ui.button("Create Task").clicked() {
                 if self.task_name.is_empty() {
                     warn!("Task name is empty.");
@@ -194,9 +665,65 @@ impl eframe::App for TaskRecorderApp {
                 self.stop_recording();
             }
 
-            if self.is_recording {
-                ui.label("Recording...");
+            if ui.button("Replay Task").clicked() {
+                self.start_playback();
+            }
+
+            ui.horizontal(|ui| {
+                ui.label("Load Session:");
+                ui.text_edit_singleline(&mut self.load_path);
+                if ui.button("Load").clicked() {
+                    self.load_session();
+                }
+            });
+
+            if *self.is_recording.lock().unwrap() {
+                ui.label("Recording... (Ctrl+Shift+R to stop)");
+            }
+
+            if *self.is_playing.lock().unwrap() {
+                ui.label("Replaying...");
             }
         });
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_debug_str_round_trips() {
+        let keys = [
+            Key::Alt,
+            Key::Backspace,
+            Key::ControlLeft,
+            Key::Return,
+            Key::Space,
+            Key::KeyA,
+            Key::KeyZ,
+            Key::Num0,
+            Key::F12,
+            Key::KpDivide,
+            Key::Unknown(65),
+        ];
+        for key in keys {
+            let debug_str = format!("{:?}", key);
+            assert_eq!(key_from_debug_str(&debug_str), Some(key));
+        }
+    }
+
+    #[test]
+    fn button_debug_str_round_trips() {
+        let buttons = [
+            Button::Left,
+            Button::Right,
+            Button::Middle,
+            Button::Unknown(7),
+        ];
+        for button in buttons {
+            let debug_str = format!("{:?}", button);
+            assert_eq!(button_from_debug_str(&debug_str), Some(button));
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,424 @@
+//! Lightweight software-KVM streaming mode: a "server" captures input locally and forwards
+//! it over TCP, and a "client" on the far end replays it with `rdev::simulate`. This reuses
+//! the same `EventData` <-> `EventType` mapping that local playback uses, just applied live
+//! instead of from a saved session.
+
+use crate::{event_data_to_event_type, process_event, EventData};
+use hmac::{Hmac, KeyInit, Mac};
+use log::{error, info, warn};
+use rdev::{listen, simulate};
+use sha2::Sha256;
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long the server waits for more `MouseMove` events before flushing a batch.
+const BATCH_WINDOW: Duration = Duration::from_millis(10);
+
+/// Number of random bytes in each handshake nonce.
+const NONCE_LEN: usize = 16;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// A message on the wire. Mouse movement is batched to avoid flooding the network with one
+/// packet per pixel of movement; every other event is sent as soon as it happens.
+#[derive(Debug, PartialEq)]
+enum Message {
+    Event(EventData),
+    Batch(Vec<EventData>),
+}
+
+impl Message {
+    const VERSION: u8 = 1;
+    const TAG_EVENT: u8 = 0;
+    const TAG_BATCH: u8 = 1;
+
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = vec![Self::VERSION];
+        match self {
+            Message::Event(data) => {
+                buf.push(Self::TAG_EVENT);
+                encode_event_data(data, &mut buf);
+            }
+            Message::Batch(events) => {
+                buf.push(Self::TAG_BATCH);
+                buf.extend_from_slice(&(events.len() as u32).to_be_bytes());
+                for data in events {
+                    encode_event_data(data, &mut buf);
+                }
+            }
+        }
+        buf
+    }
+
+    fn decode(buf: &[u8]) -> Option<Message> {
+        let version = *buf.first()?;
+        if version != Self::VERSION {
+            warn!("Ignoring message with unsupported wire version {}", version);
+            return None;
+        }
+        let tag = *buf.get(1)?;
+        let rest = &buf[2..];
+        match tag {
+            Self::TAG_EVENT => {
+                let (data, _) = decode_event_data(rest)?;
+                Some(Message::Event(data))
+            }
+            Self::TAG_BATCH => {
+                let count = u32::from_be_bytes(rest.get(0..4)?.try_into().ok()?) as usize;
+                let mut cursor = rest.get(4..)?;
+                // Every encoded EventData is at least this many bytes (five empty strings'
+                // length prefixes plus the two u64 timestamps), so a peer can't make us
+                // reserve more capacity than the frame could possibly contain.
+                const MIN_EVENT_DATA_LEN: usize = 5 * 2 + 8 + 8;
+                if count > cursor.len() / MIN_EVENT_DATA_LEN {
+                    warn!("Ignoring batch claiming {} events in a {}-byte frame.", count, cursor.len());
+                    return None;
+                }
+                let mut events = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let (data, consumed) = decode_event_data(cursor)?;
+                    events.push(data);
+                    cursor = &cursor[consumed..];
+                }
+                Some(Message::Batch(events))
+            }
+            _ => {
+                warn!("Ignoring message with unknown tag {}", tag);
+                None
+            }
+        }
+    }
+}
+
+fn encode_string(s: &str, buf: &mut Vec<u8>) {
+    buf.extend_from_slice(&(s.len() as u16).to_be_bytes());
+    buf.extend_from_slice(s.as_bytes());
+}
+
+fn decode_string(buf: &[u8]) -> Option<(String, usize)> {
+    let len = u16::from_be_bytes(buf.get(0..2)?.try_into().ok()?) as usize;
+    let bytes = buf.get(2..2 + len)?;
+    let s = String::from_utf8(bytes.to_vec()).ok()?;
+    Some((s, 2 + len))
+}
+
+fn encode_event_data(data: &EventData, buf: &mut Vec<u8>) {
+    encode_string(&data.event_type, buf);
+    encode_string(&data.button_or_key, buf);
+    encode_string(&data.action, buf);
+    encode_string(&data.position, buf);
+    encode_string(&data.timestamp, buf);
+    buf.extend_from_slice(&data.offset_micros.to_be_bytes());
+    buf.extend_from_slice(&data.epoch_micros.to_be_bytes());
+}
+
+fn decode_event_data(buf: &[u8]) -> Option<(EventData, usize)> {
+    let mut cursor = 0usize;
+    let (event_type, n) = decode_string(&buf[cursor..])?;
+    cursor += n;
+    let (button_or_key, n) = decode_string(&buf[cursor..])?;
+    cursor += n;
+    let (action, n) = decode_string(&buf[cursor..])?;
+    cursor += n;
+    let (position, n) = decode_string(&buf[cursor..])?;
+    cursor += n;
+    let (timestamp, n) = decode_string(&buf[cursor..])?;
+    cursor += n;
+    let offset_micros = u64::from_be_bytes(buf.get(cursor..cursor + 8)?.try_into().ok()?);
+    cursor += 8;
+    let epoch_micros = u64::from_be_bytes(buf.get(cursor..cursor + 8)?.try_into().ok()?);
+    cursor += 8;
+
+    Some((
+        EventData {
+            event_type,
+            button_or_key,
+            action,
+            position,
+            timestamp,
+            offset_micros,
+            epoch_micros,
+        },
+        cursor,
+    ))
+}
+
+fn write_bytes(stream: &mut TcpStream, bytes: &[u8]) -> io::Result<()> {
+    stream.write_all(&(bytes.len() as u16).to_be_bytes())?;
+    stream.write_all(bytes)
+}
+
+fn read_bytes(stream: &mut TcpStream) -> io::Result<Vec<u8>> {
+    let mut len_buf = [0u8; 2];
+    stream.read_exact(&mut len_buf)?;
+    let len = u16::from_be_bytes(len_buf) as usize;
+    let mut bytes = vec![0u8; len];
+    stream.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn random_nonce() -> [u8; NONCE_LEN] {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::fill(&mut nonce);
+    nonce
+}
+
+/// `HMAC-SHA256(secret, nonce)`, proving knowledge of `secret` without ever transmitting it.
+fn hmac_tag(secret: &str, nonce: &[u8]) -> Vec<u8> {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts a key of any length");
+    mac.update(nonce);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn verify_tag(secret: &str, nonce: &[u8], tag: &[u8]) -> bool {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts a key of any length");
+    mac.update(nonce);
+    mac.verify_slice(tag).is_ok()
+}
+
+/// Server side of the mutual challenge-response handshake: proves we know `secret` to the
+/// client and checks the client knows it too, without either side ever putting the secret
+/// itself on the wire. Returns `Ok(true)` once both directions are verified.
+fn server_handshake(stream: &mut TcpStream, secret: &str) -> io::Result<bool> {
+    let server_nonce = random_nonce();
+    write_bytes(stream, &server_nonce)?;
+
+    let client_tag = read_bytes(stream)?;
+    let client_nonce = read_bytes(stream)?;
+    if !verify_tag(secret, &server_nonce, &client_tag) {
+        return Ok(false);
+    }
+
+    write_bytes(stream, &hmac_tag(secret, &client_nonce))?;
+    Ok(true)
+}
+
+/// Client side of the mutual challenge-response handshake; see [`server_handshake`].
+fn client_handshake(stream: &mut TcpStream, secret: &str) -> io::Result<bool> {
+    let server_nonce = read_bytes(stream)?;
+    let client_nonce = random_nonce();
+    write_bytes(stream, &hmac_tag(secret, &server_nonce))?;
+    write_bytes(stream, &client_nonce)?;
+
+    let server_tag = read_bytes(stream)?;
+    Ok(verify_tag(secret, &client_nonce, &server_tag))
+}
+
+fn write_frame(stream: &mut TcpStream, message: &Message) -> io::Result<()> {
+    let body = message.encode();
+    stream.write_all(&(body.len() as u32).to_be_bytes())?;
+    stream.write_all(&body)
+}
+
+fn read_frame(stream: &mut TcpStream) -> io::Result<Option<Message>> {
+    let mut len_buf = [0u8; 4];
+    if let Err(err) = stream.read_exact(&mut len_buf) {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            return Ok(None);
+        }
+        return Err(err);
+    }
+    let len = u32::from_be_bytes(len_buf) as usize;
+    let mut body = vec![0u8; len];
+    stream.read_exact(&mut body)?;
+    Ok(Message::decode(&body))
+}
+
+/// Captures local input and streams it to whichever client is connected, batching rapid
+/// mouse movement so the socket isn't flooded with one packet per pixel. Every connection
+/// must present `secret` before any events are forwarded, since this is otherwise an
+/// unauthenticated keylogger/input-injection channel.
+pub(crate) fn run_server(addr: &str, secret: &str) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    info!("Streaming server listening on {}", addr);
+
+    let (sender, receiver) = mpsc::channel::<EventData>();
+    let last_position = Arc::new(Mutex::new((0.0, 0.0)));
+    let start = Instant::now();
+
+    thread::spawn(move || {
+        info!("Streaming capture thread started.");
+        if let Err(err) = listen(move |event| {
+            if let Some(data) = process_event(event, start, &last_position) {
+                if sender.send(data).is_err() {
+                    warn!("Streaming receiver dropped; capture events will be discarded.");
+                }
+            }
+        }) {
+            error!("Error capturing input for streaming: {:?}", err);
+        }
+    });
+
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                error!("Failed to accept streaming client: {:?}", err);
+                continue;
+            }
+        };
+        info!("Streaming client connected: {:?}", stream.peer_addr());
+
+        match server_handshake(&mut stream, secret) {
+            Ok(true) => {}
+            Ok(false) => {
+                warn!(
+                    "Rejecting streaming client {:?}: secret did not match.",
+                    stream.peer_addr()
+                );
+                continue;
+            }
+            Err(err) => {
+                warn!("Handshake with streaming client failed: {:?}", err);
+                continue;
+            }
+        }
+
+        let mut pending_moves: Vec<EventData> = Vec::new();
+        loop {
+            match receiver.recv_timeout(BATCH_WINDOW) {
+                Ok(data) => {
+                    if data.button_or_key == "Move" {
+                        pending_moves.push(data);
+                        continue;
+                    }
+                    if !pending_moves.is_empty() && flush_batch(&mut stream, &mut pending_moves).is_err() {
+                        break;
+                    }
+                    if write_frame(&mut stream, &Message::Event(data)).is_err() {
+                        warn!("Streaming client disconnected.");
+                        break;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    if !pending_moves.is_empty() && flush_batch(&mut stream, &mut pending_moves).is_err() {
+                        break;
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    warn!("Capture thread exited; stopping streaming server.");
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn flush_batch(stream: &mut TcpStream, pending_moves: &mut Vec<EventData>) -> io::Result<()> {
+    let batch = std::mem::take(pending_moves);
+    write_frame(stream, &Message::Batch(batch))
+}
+
+/// Connects to a streaming server and replays every event it forwards in real time. Refuses
+/// to replay anything unless the server proves it knows `secret` first, so this can't be
+/// pointed at an arbitrary/unauthenticated host to inject input.
+pub(crate) fn run_client(addr: &str, secret: &str) -> io::Result<()> {
+    let mut stream = TcpStream::connect(addr)?;
+    info!("Connected to streaming server at {}", addr);
+
+    if !client_handshake(&mut stream, secret)? {
+        error!("Refusing to stream: server did not prove it knows the expected shared secret.");
+        return Err(io::Error::new(io::ErrorKind::PermissionDenied, "handshake secret mismatch"));
+    }
+
+    loop {
+        match read_frame(&mut stream)? {
+            Some(Message::Event(data)) => replay(&data),
+            Some(Message::Batch(events)) => {
+                for data in &events {
+                    replay(data);
+                }
+            }
+            None => {
+                info!("Streaming server closed the connection.");
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn replay(data: &EventData) {
+    match event_data_to_event_type(data) {
+        Some(event_type) => {
+            if let Err(err) = simulate(&event_type) {
+                error!("Failed to apply streamed event {:?}: {:?}", event_type, err);
+            }
+        }
+        None => {
+            warn!(
+                "Skipping unrecognized streamed event: {} {}",
+                data.event_type, data.button_or_key
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_event(suffix: &str) -> EventData {
+        EventData {
+            event_type: format!("Mouse{}", suffix),
+            button_or_key: "Move".to_string(),
+            action: "Move".to_string(),
+            position: "(12, 34)".to_string(),
+            timestamp: "2026-07-26T00:00:00".to_string(),
+            offset_micros: 123_456,
+            epoch_micros: 1_800_000_000_000_000,
+        }
+    }
+
+    #[test]
+    fn event_message_round_trips() {
+        let message = Message::Event(sample_event(""));
+        let decoded = Message::decode(&message.encode()).expect("decodes what we just encoded");
+        assert_eq!(message, decoded);
+    }
+
+    #[test]
+    fn batch_message_round_trips() {
+        let events = vec![sample_event("A"), sample_event("B"), sample_event("C")];
+        let message = Message::Batch(events);
+        let decoded = Message::decode(&message.encode()).expect("decodes what we just encoded");
+        assert_eq!(message, decoded);
+    }
+
+    #[test]
+    fn empty_batch_round_trips() {
+        let message = Message::Batch(Vec::new());
+        let decoded = Message::decode(&message.encode()).expect("decodes what we just encoded");
+        assert_eq!(message, decoded);
+    }
+
+    #[test]
+    fn batch_rejects_count_that_cant_fit_in_the_frame() {
+        let mut buf = vec![Message::VERSION, Message::TAG_BATCH];
+        // Claim a huge number of events but back it with no event bytes at all.
+        buf.extend_from_slice(&u32::MAX.to_be_bytes());
+        assert!(Message::decode(&buf).is_none());
+    }
+
+    #[test]
+    fn handshake_succeeds_when_both_sides_know_the_secret() {
+        let secret = "correct horse battery staple";
+        let nonce = random_nonce();
+        let tag = hmac_tag(secret, &nonce);
+        assert!(verify_tag(secret, &nonce, &tag));
+    }
+
+    #[test]
+    fn handshake_fails_on_wrong_secret() {
+        let nonce = random_nonce();
+        let tag = hmac_tag("correct secret", &nonce);
+        assert!(!verify_tag("wrong secret", &nonce, &tag));
+    }
+}
@@ -0,0 +1,155 @@
+//! Structured session persistence: unlike the CSV export, JSON and RON round-trip cleanly
+//! through serde, so a saved session can be loaded back and fed into the playback engine.
+
+use crate::EventData;
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// A recorded session: the task it was recorded for, when it started, and its events.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Session {
+    pub(crate) task_name: String,
+    pub(crate) start_epoch_micros: u64,
+    pub(crate) events: Vec<EventData>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExportFormat {
+    Csv,
+    Json,
+    Ron,
+}
+
+impl ExportFormat {
+    pub(crate) fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Ron => "ron",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Json => "JSON",
+            ExportFormat::Ron => "RON",
+        }
+    }
+
+    pub(crate) const ALL: [ExportFormat; 3] = [ExportFormat::Csv, ExportFormat::Json, ExportFormat::Ron];
+}
+
+/// Saves `events` in the chosen format, named `<task_name>_events.<extension>`.
+pub(crate) fn save(task_name: &str, events: &[EventData], format: ExportFormat) {
+    match format {
+        ExportFormat::Csv => crate::save_events_to_csv(task_name, events),
+        ExportFormat::Json | ExportFormat::Ron => save_structured(task_name, events, format),
+    }
+}
+
+fn save_structured(task_name: &str, events: &[EventData], format: ExportFormat) {
+    let session = Session {
+        task_name: task_name.to_string(),
+        start_epoch_micros: events.first().map_or(0, |event| event.epoch_micros),
+        events: events.to_vec(),
+    };
+    let filename = format!("{}_events.{}", task_name, format.extension());
+
+    let serialized = match format {
+        ExportFormat::Json => serde_json::to_string_pretty(&session).map_err(|err| err.to_string()),
+        ExportFormat::Ron => {
+            ron::ser::to_string_pretty(&session, ron::ser::PrettyConfig::default())
+                .map_err(|err| err.to_string())
+        }
+        ExportFormat::Csv => unreachable!("CSV is handled by save_events_to_csv"),
+    };
+
+    match serialized {
+        Ok(contents) => match fs::write(&filename, contents) {
+            Ok(()) => info!("Session saved to {}", filename),
+            Err(err) => error!("Failed to write session file {}: {:?}", filename, err),
+        },
+        Err(err) => error!("Failed to serialize session as {}: {}", format.label(), err),
+    }
+}
+
+/// Loads a `.json` or `.ron` session file, returning its task name and events.
+pub(crate) fn load(path: &str) -> Option<(String, Vec<EventData>)> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            error!("Failed to read session file {}: {:?}", path, err);
+            return None;
+        }
+    };
+
+    let session: Session = if path.ends_with(".json") {
+        match serde_json::from_str(&contents) {
+            Ok(session) => session,
+            Err(err) => {
+                error!("Failed to parse JSON session {}: {:?}", path, err);
+                return None;
+            }
+        }
+    } else if path.ends_with(".ron") {
+        match ron::from_str(&contents) {
+            Ok(session) => session,
+            Err(err) => {
+                error!("Failed to parse RON session {}: {:?}", path, err);
+                return None;
+            }
+        }
+    } else {
+        error!("Unsupported session file extension: {}", path);
+        return None;
+    };
+
+    info!(
+        "Loaded session '{}' with {} event(s) from {}",
+        session.task_name,
+        session.events.len(),
+        path
+    );
+    Some((session.task_name, session.events))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_events() -> Vec<EventData> {
+        vec![EventData {
+            event_type: "Mouse".to_string(),
+            button_or_key: "Left".to_string(),
+            action: "Pressed".to_string(),
+            position: "(12, 34)".to_string(),
+            timestamp: "2026-07-26T00:00:00".to_string(),
+            offset_micros: 123_456,
+            epoch_micros: 1_800_000_000_000_000,
+        }]
+    }
+
+    fn round_trip(task_name: &str, format: ExportFormat) {
+        let events = sample_events();
+        save(task_name, &events, format);
+        let filename = format!("{}_events.{}", task_name, format.extension());
+
+        let (loaded_task_name, loaded_events) = load(&filename).expect("just-saved session loads");
+        assert_eq!(loaded_task_name, task_name);
+        assert_eq!(loaded_events, events);
+
+        fs::remove_file(&filename).expect("remove session file written by the test");
+    }
+
+    #[test]
+    fn json_session_round_trips() {
+        round_trip("test_session_json", ExportFormat::Json);
+    }
+
+    #[test]
+    fn ron_session_round_trips() {
+        round_trip("test_session_ron", ExportFormat::Ron);
+    }
+}
@@ -0,0 +1,126 @@
+//! Scriptable triggers: a user-registered rule fires a shell command whenever a captured
+//! event matches its predicate (e.g. "middle mouse button pressed" or "F12 released").
+
+use crate::EventData;
+use log::{error, info};
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// Loads trigger rules from a simple config file (one rule per line):
+/// `event_type,button_or_key,action,silent,command,arg1;arg2;...`
+///
+/// Example: `Mouse,Middle,Pressed,true,notify-send,Middle click detected`
+///
+/// Missing files just mean no rules are configured, not an error.
+pub(crate) fn load_from_file(path: &str) -> Vec<TriggerRule> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            info!("No trigger rules loaded from {}: {:?}", path, err);
+            return Vec::new();
+        }
+    };
+
+    let rules: Vec<TriggerRule> = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(parse_rule_line)
+        .collect();
+
+    info!("Loaded {} trigger rule(s) from {}", rules.len(), path);
+    rules
+}
+
+fn parse_rule_line(line: &str) -> Option<TriggerRule> {
+    let mut fields = line.splitn(6, ',');
+    let event_type = fields.next()?.trim().to_string();
+    let button_or_key = fields.next()?.trim().to_string();
+    let action = fields.next()?.trim().to_string();
+    let silent = fields.next()?.trim().eq_ignore_ascii_case("true");
+    let command = fields.next()?.trim().to_string();
+    let args = fields
+        .next()
+        .map(|rest| rest.split(';').map(|arg| arg.trim().to_string()).collect())
+        .unwrap_or_default();
+
+    Some(TriggerRule {
+        event_type,
+        button_or_key,
+        action,
+        command,
+        args,
+        silent,
+    })
+}
+
+/// A single "when this event happens, run this command" rule.
+#[derive(Clone)]
+pub(crate) struct TriggerRule {
+    /// "Keyboard" or "Mouse", matched against `EventData::event_type`.
+    pub(crate) event_type: String,
+    /// The key or button name, e.g. "F12" or "Middle".
+    pub(crate) button_or_key: String,
+    /// "Pressed" or "Released".
+    pub(crate) action: String,
+    pub(crate) command: String,
+    pub(crate) args: Vec<String>,
+    /// When true, the child's stdio is routed to null instead of inherited from this process.
+    pub(crate) silent: bool,
+}
+
+impl TriggerRule {
+    fn matches(&self, data: &EventData) -> bool {
+        self.event_type == data.event_type
+            && self.button_or_key == data.button_or_key
+            && self.action == data.action
+    }
+}
+
+/// Checks `data` against every rule and fires the matching ones. Each command runs on its
+/// own worker thread so a slow or hanging process can't stall event capture.
+pub(crate) fn evaluate(rules: &[TriggerRule], data: &EventData) {
+    for rule in rules {
+        if rule.matches(data) {
+            fire(rule.clone(), data.clone());
+        }
+    }
+}
+
+fn fire(rule: TriggerRule, data: EventData) {
+    thread::spawn(move || {
+        info!("Trigger matched; running command: {} {:?}", rule.command, rule.args);
+
+        let mut command = Command::new(&rule.command);
+        command
+            .args(&rule.args)
+            .env("DOCKTRACK_EVENT_TYPE", &data.event_type)
+            .env("DOCKTRACK_BUTTON_OR_KEY", &data.button_or_key)
+            .env("DOCKTRACK_ACTION", &data.action)
+            .env("DOCKTRACK_POSITION", &data.position)
+            .env("DOCKTRACK_TIMESTAMP", &data.timestamp);
+
+        if rule.silent {
+            command
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null());
+        } else {
+            command
+                .stdin(Stdio::inherit())
+                .stdout(Stdio::inherit())
+                .stderr(Stdio::inherit());
+        }
+
+        match command.spawn() {
+            Ok(mut child) => {
+                if let Err(err) = child.wait() {
+                    error!("Trigger command '{}' failed: {:?}", rule.command, err);
+                }
+            }
+            Err(err) => {
+                error!("Failed to spawn trigger command '{}': {:?}", rule.command, err);
+            }
+        }
+    });
+}